@@ -1,6 +1,12 @@
 use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
 
-use chrono::{FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, ParseError, TimeZone};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
 use clap::{builder::ArgPredicate, Parser, ValueEnum};
 use cli_clipboard::{ClipboardContext, ClipboardProvider};
 use prettytable::{format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR, Table};
@@ -62,38 +68,61 @@ const STYLE_HELP: [[&str; 5]; 8] = [
 #[derive(Parser, Debug)]
 #[command(version)]
 struct Cli {
-    /// Date/time string in the local timezone to convert to a discord timestamp
+    /// Date/time string, interpreted in the effective timezone (see `--timezone`/`--utc`,
+    /// defaults to the system local timezone), to convert to a discord timestamp
     #[arg(
         index = 1,
         default_value_if("help_style", ArgPredicate::IsPresent, ""),
-        conflicts_with = "help_style"
+        default_value_if("reference", ArgPredicate::IsPresent, ""),
+        default_value_if("file", ArgPredicate::IsPresent, ""),
+        conflicts_with_all = ["help_style", "reference", "file"]
     )]
     input: String,
 
     /// Format style of the output. (Use --help-style for style options.)
-    #[arg(index = 2, default_value = "default", value_parser = Style::parse, env = "DT_STYLE")]
+    ///
+    /// Not positional, so it composes with `--reference`/`--file`, e.g.
+    /// `discord-timestamp --reference ./log.txt --style R`.
+    #[arg(short = 's', long = "style", default_value = "default", value_parser = Style::parse, env = "DT_STYLE")]
     style: Style,
 
     /// Copy the result to the clipboard when complete
     #[arg(short = 'c', long)]
     copy_to_clipboard: bool,
 
-    /// Format string for parsing datetimes
+    /// Format string(s) for parsing the input, with optional trailing sections marked by
+    /// brackets, e.g. `%Y-%m-%d[ %H:%M[:%S]]` matches a bare date or either datetime form.
+    /// Candidates are tried from most to least specific; may be repeated to supply several
+    /// independent formats, tried in the order given.
     #[arg(
         short = 'f',
-        long,
-        default_value = "%Y-%m-%d %H:%M:%S",
-        env = "DT_DATETIME_FORMAT"
+        long = "format",
+        default_value = "%Y-%m-%d[ %H:%M[:%S]]",
+        env = "DT_FORMAT"
     )]
-    datetime_format: String,
+    formats: Vec<String>,
 
-    /// Format string for parsing lone dates (assumes midnight)
-    #[arg(short = 'd', long, default_value = "%Y-%m-%d", env = "DT_DATE_FORMAT")]
-    date_format: String,
+    /// Timezone to interpret the input in: a fixed offset (e.g. `+02:00`) or an IANA name
+    /// (e.g. `America/New_York`). Defaults to the system local timezone.
+    #[arg(short = 'z', long, env = "DT_TIMEZONE", conflicts_with = "utc")]
+    timezone: Option<String>,
 
-    /// Format string for parsing lone times (assumes today)
-    #[arg(short = 't', long, default_value = "%H:%M:%S", env = "DT_TIME_FORMAT")]
-    time_format: String,
+    /// Shortcut for `--timezone UTC`
+    #[arg(short = 'u', long)]
+    utc: bool,
+
+    /// Use a file's last-modification time as the input instead of a date/time string
+    #[arg(long, conflicts_with = "file")]
+    reference: Option<std::path::PathBuf>,
+
+    /// Read one date/time string per line from FILE (use `-` for stdin) and emit a timestamp
+    /// for each, instead of processing a single input
+    #[arg(long)]
+    file: Option<std::path::PathBuf>,
+
+    /// Clock format (12 or 24 hour) used for the local preview of date/time styles
+    #[arg(long, default_value = "12", env = "DT_CLOCK")]
+    clock: Clock,
 
     /// Shows options (and abbreviations) for the style argument
     #[arg(long)]
@@ -101,24 +130,317 @@ struct Cli {
 }
 
 impl Cli {
-    fn get_naive_datetime(&self) -> Result<NaiveDateTime, ParseError> {
-        // Try to parse a full datetime
-        let datetime = NaiveDateTime::parse_from_str(&self.input, &self.datetime_format);
-        if datetime.is_ok() {
-            return datetime;
+    /// Parses `input` as a date/time, anchoring relative expressions ("today", "next friday",
+    /// ...) and bare times to the wall-clock `now` in the effective output timezone
+    fn get_naive_datetime(&self, input: &str, now: NaiveDateTime) -> Result<NaiveDateTime, String> {
+        for format in &self.formats {
+            for pattern in expand_format(format)? {
+                if let Ok(datetime) = NaiveDateTime::parse_from_str(input, &pattern) {
+                    return Ok(datetime);
+                }
+
+                if let Ok(date) = NaiveDate::parse_from_str(input, &pattern) {
+                    return Ok(date.and_hms_opt(0, 0, 0).unwrap());
+                }
+
+                if let Ok(time) = NaiveTime::parse_from_str(input, &pattern) {
+                    return Ok(NaiveDateTime::new(now.date(), time));
+                }
+            }
+        }
+
+        // Try to parse a relative/natural-language expression (e.g. "in 3 days", "next friday")
+        parse_relative_datetime(input, now)
+            .ok_or_else(|| format!("Could not parse '{}' using any configured format", input))
+    }
+
+    /// The effective timezone to interpret the parsed input in, based on `--utc`/`--timezone`
+    fn zone(&self) -> Result<Zone, String> {
+        if self.utc {
+            return Ok(Zone::Fixed(FixedOffset::east_opt(0).unwrap()));
+        }
+
+        match &self.timezone {
+            Some(timezone) => Zone::parse(timezone),
+            None => Ok(Zone::Local),
+        }
+    }
+}
+
+/// A timezone selected via `--timezone`/`--utc`, or the system local timezone by default
+#[derive(Debug, Clone, Copy)]
+enum Zone {
+    Local,
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+impl Zone {
+    fn parse(s: &str) -> Result<Self, String> {
+        if let Some(offset) = parse_fixed_offset(s) {
+            return Ok(Zone::Fixed(offset));
+        }
+
+        s.parse::<Tz>()
+            .map(Zone::Named)
+            .map_err(|_| format!("Unrecognized timezone: {}", s))
+    }
+
+    /// Resolves a locally-typed `naive` datetime to a concrete instant in this timezone,
+    /// erroring on DST transitions where the local time is ambiguous or does not exist
+    fn resolve(&self, naive: NaiveDateTime) -> Result<DateTime<FixedOffset>, String> {
+        match self {
+            Zone::Local => Local
+                .from_local_datetime(&naive)
+                .single()
+                .map(|dt| dt.fixed_offset())
+                .ok_or_else(|| format!("{} is ambiguous or does not exist in the local timezone", naive)),
+            Zone::Fixed(offset) => offset
+                .from_local_datetime(&naive)
+                .single()
+                .ok_or_else(|| format!("{} is ambiguous or does not exist at offset {}", naive, offset)),
+            Zone::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .single()
+                .map(|dt| dt.fixed_offset())
+                .ok_or_else(|| format!("{} is ambiguous or does not exist in {}", naive, tz)),
+        }
+    }
+
+    /// Renders an already-known `instant` (e.g. a file's modification time) for display
+    /// in this timezone
+    fn display(&self, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).fixed_offset(),
+            Zone::Fixed(offset) => instant.with_timezone(offset),
+            Zone::Named(tz) => instant.with_timezone(tz).fixed_offset(),
         }
+    }
 
-        // Try to parse just a date
-        if let Ok(date) = NaiveDate::parse_from_str(&self.input, &self.date_format) {
-            return Ok(date.and_hms_opt(0, 0, 0).unwrap());
+    /// The current wall-clock time in this timezone, used to anchor relative date expressions
+    fn now(&self) -> NaiveDateTime {
+        match self {
+            Zone::Local => Local::now().naive_local(),
+            Zone::Fixed(offset) => Utc::now().with_timezone(offset).naive_local(),
+            Zone::Named(tz) => Utc::now().with_timezone(tz).naive_local(),
         }
+    }
+}
+
+/// Parses a fixed offset like `+02:00` or `-0530`
+fn parse_fixed_offset(s: &str) -> Option<FixedOffset> {
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+
+    let rest = rest.replace(':', "");
+    if !rest.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes): (i32, i32) = match rest.len() {
+        2 => (rest[0..2].parse().ok()?, 0),
+        4 => (rest[0..2].parse().ok()?, rest[2..4].parse().ok()?),
+        _ => return None,
+    };
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
 
-        // Try to parse just a time
-        NaiveTime::parse_from_str(&self.input, &self.time_format).map(|time| {
-            let today = Local::now().date_naive();
-            NaiveDateTime::new(today, time)
-        })
+/// A fragment of a bracketed format string: a literal strftime fragment, or an optional
+/// section (which may itself contain nested optional sections)
+#[derive(Debug, Clone)]
+enum FormatNode {
+    Literal(String),
+    Optional(Vec<FormatNode>),
+}
+
+/// Expands a format string with optional bracketed sections, e.g. `%Y-%m-%d[ %H:%M[:%S]]`,
+/// into concrete strftime patterns ordered from most-specific (all optionals present) to
+/// least-specific (all optionals dropped)
+fn expand_format(format: &str) -> Result<Vec<String>, String> {
+    let mut chars = format.chars().peekable();
+    let (nodes, closed) = parse_format_nodes(&mut chars);
+    if closed {
+        return Err(format!("unmatched ']' in format string '{}'", format));
     }
+
+    Ok(expand_format_nodes(&nodes))
+}
+
+/// Recursively scans a format string into a tree of [`FormatNode`]s, returning whether
+/// the scan stopped at a closing `]` (as opposed to the end of the string)
+fn parse_format_nodes(chars: &mut std::iter::Peekable<std::str::Chars>) -> (Vec<FormatNode>, bool) {
+    let mut nodes = Vec::new();
+    let mut literal = String::new();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '[' => {
+                chars.next();
+                if !literal.is_empty() {
+                    nodes.push(FormatNode::Literal(std::mem::take(&mut literal)));
+                }
+                let (inner, _) = parse_format_nodes(chars);
+                nodes.push(FormatNode::Optional(inner));
+            }
+            ']' => {
+                chars.next();
+                if !literal.is_empty() {
+                    nodes.push(FormatNode::Literal(std::mem::take(&mut literal)));
+                }
+                return (nodes, true);
+            }
+            _ => {
+                literal.push(c);
+                chars.next();
+            }
+        }
+    }
+
+    if !literal.is_empty() {
+        nodes.push(FormatNode::Literal(literal));
+    }
+    (nodes, false)
+}
+
+/// Expands a tree of [`FormatNode`]s into concrete strftime patterns, most-specific first
+fn expand_format_nodes(nodes: &[FormatNode]) -> Vec<String> {
+    let Some((first, rest)) = nodes.split_first() else {
+        return vec![String::new()];
+    };
+    let rest_expansions = expand_format_nodes(rest);
+
+    match first {
+        FormatNode::Literal(literal) => rest_expansions
+            .into_iter()
+            .map(|rest| format!("{}{}", literal, rest))
+            .collect(),
+        FormatNode::Optional(inner) => {
+            let mut expansions: Vec<String> = expand_format_nodes(inner)
+                .into_iter()
+                .flat_map(|inner| {
+                    rest_expansions
+                        .iter()
+                        .map(move |rest| format!("{}{}", inner, rest))
+                })
+                .collect();
+            expansions.extend(rest_expansions);
+            expansions
+        }
+    }
+}
+
+/// Parses human-friendly relative and natural-language date expressions
+/// (`in 2 hours`, `3 days ago`, `tomorrow`, `next friday`, `tomorrow 18:00`, ...) relative to
+/// `anchor`.
+fn parse_relative_datetime(input: &str, anchor: NaiveDateTime) -> Option<NaiveDateTime> {
+    let lower = input.trim().to_lowercase();
+
+    if let Some(datetime) = parse_relative_keyword(&lower, anchor) {
+        return Some(datetime);
+    }
+
+    // A keyword or weekday followed by a time-of-day, e.g. "tomorrow 18:00"
+    if let Some((keyword, time)) = lower.rsplit_once(' ') {
+        if let (Some(date), Some(time)) =
+            (parse_relative_keyword(keyword, anchor), parse_time_of_day(time))
+        {
+            return Some(NaiveDateTime::new(date.date(), time));
+        }
+    }
+
+    let tokens: Vec<&str> = lower.split_whitespace().collect();
+    match tokens.as_slice() {
+        ["in", amount, unit] => apply_offset(anchor, amount.parse().ok()?, unit),
+        [amount, unit, "ago"] => apply_offset(anchor, -amount.parse::<i64>().ok()?, unit),
+        _ => None,
+    }
+}
+
+/// Matches a bare keyword (`now`, `today`, `tomorrow`, `yesterday`) or a (possibly
+/// `next `-prefixed) weekday name, resolved relative to `anchor`
+fn parse_relative_keyword(s: &str, anchor: NaiveDateTime) -> Option<NaiveDateTime> {
+    match s {
+        "now" | "today" => return Some(anchor),
+        "tomorrow" => return Some(anchor + Duration::days(1)),
+        "yesterday" => return Some(anchor - Duration::days(1)),
+        _ => {}
+    }
+
+    parse_weekday(s).map(|weekday| next_weekday(anchor, weekday))
+}
+
+/// Parses a bare time-of-day like `18:00` or `18:00:00`
+fn parse_time_of_day(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(s, "%H:%M"))
+        .ok()
+}
+
+/// Parses a (possibly `next `-prefixed) weekday name
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    let s = s.strip_prefix("next ").unwrap_or(s);
+    match s {
+        "monday" => Some(Weekday::Mon),
+        "tuesday" => Some(Weekday::Tue),
+        "wednesday" => Some(Weekday::Wed),
+        "thursday" => Some(Weekday::Thu),
+        "friday" => Some(Weekday::Fri),
+        "saturday" => Some(Weekday::Sat),
+        "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Advances `anchor` to the next occurrence of `weekday`, always moving at least one day
+fn next_weekday(anchor: NaiveDateTime, weekday: Weekday) -> NaiveDateTime {
+    let mut days = (weekday.num_days_from_monday() as i64
+        - anchor.weekday().num_days_from_monday() as i64)
+        .rem_euclid(7);
+    if days == 0 {
+        days = 7;
+    }
+    anchor + Duration::days(days)
+}
+
+/// Applies a signed `amount` of `unit`s (singular or plural, e.g. `day`/`days`) to `anchor`,
+/// returning `None` instead of panicking if `amount` over/underflows the representable range
+fn apply_offset(anchor: NaiveDateTime, amount: i64, unit: &str) -> Option<NaiveDateTime> {
+    match unit.strip_suffix('s').unwrap_or(unit) {
+        "second" => anchor.checked_add_signed(Duration::try_seconds(amount)?),
+        "minute" => anchor.checked_add_signed(Duration::try_minutes(amount)?),
+        "hour" => anchor.checked_add_signed(Duration::try_hours(amount)?),
+        "day" => anchor.checked_add_signed(Duration::try_days(amount)?),
+        "week" => anchor.checked_add_signed(Duration::try_weeks(amount)?),
+        "month" => add_months(anchor, amount),
+        "year" => add_months(anchor, amount.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+/// Adds `months` to `anchor`, clamping the day-of-month if it overflows the target month
+/// (e.g. Jan 31 + 1 month -> Feb 28/29)
+fn add_months(anchor: NaiveDateTime, months: i64) -> Option<NaiveDateTime> {
+    let total_months = (anchor.year() as i64)
+        .checked_mul(12)?
+        .checked_add(anchor.month() as i64 - 1)?
+        .checked_add(months)?;
+    let year = i32::try_from(total_months.div_euclid(12)).ok()?;
+    let month = (total_months.rem_euclid(12) + 1) as u32;
+
+    let days_in_month = if month == 12 {
+        NaiveDate::from_ymd_opt(year.checked_add(1)?, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }?
+    .pred_opt()?
+    .day();
+
+    let date = NaiveDate::from_ymd_opt(year, month, anchor.day().min(days_in_month))?;
+    Some(NaiveDateTime::new(date, anchor.time()))
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -183,6 +505,81 @@ impl Style {
             _ => Err("Expected one of: default, short-time, t, long-time, T, short-date, d, long-date, D, short-date-time, f, long-date-time, F, relative-time, R".into()),
         }
     }
+
+    /// Renders a local preview of how Discord will display this style for `datetime`
+    fn preview(&self, datetime: &DateTime<FixedOffset>, clock: Clock) -> String {
+        let time = match clock {
+            Clock::Twelve => datetime.format("%-I:%M %p").to_string(),
+            Clock::TwentyFour => datetime.format("%H:%M").to_string(),
+        };
+        let long_time = match clock {
+            Clock::Twelve => datetime.format("%-I:%M:%S %p").to_string(),
+            Clock::TwentyFour => datetime.format("%H:%M:%S").to_string(),
+        };
+        let (date, short_date) = match clock {
+            Clock::Twelve => (
+                datetime.format("%B %-d, %Y").to_string(),
+                datetime.format("%m/%d/%Y").to_string(),
+            ),
+            Clock::TwentyFour => (
+                datetime.format("%-d %B %Y").to_string(),
+                datetime.format("%d/%m/%Y").to_string(),
+            ),
+        };
+
+        match self {
+            Style::Default | Style::ShortDateTime => format!("{} {}", date, time),
+            Style::ShortTime => time,
+            Style::LongTime => long_time,
+            Style::ShortDate => short_date,
+            Style::LongDate => date,
+            Style::LongDateTime => format!("{}, {} {}", datetime.format("%A"), date, time),
+            Style::RelativeTime => relative_phrase(datetime),
+        }
+    }
+}
+
+/// Clock format used when rendering the local preview of a date/time style
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Clock {
+    #[default]
+    #[value(name = "12")]
+    Twelve,
+    #[value(name = "24")]
+    TwentyFour,
+}
+
+/// Computes the phrase Discord would render for [`Style::RelativeTime`], e.g. "in 3 hours"
+/// or "2 days ago", relative to now
+fn relative_phrase(datetime: &DateTime<FixedOffset>) -> String {
+    const UNITS: [(&str, i64); 7] = [
+        ("year", 31536000),
+        ("month", 2592000),
+        ("week", 604800),
+        ("day", 86400),
+        ("hour", 3600),
+        ("minute", 60),
+        ("second", 1),
+    ];
+
+    let delta = datetime.timestamp() - Local::now().timestamp();
+    if delta == 0 {
+        return "now".to_string();
+    }
+
+    for (unit, seconds) in UNITS {
+        if delta.abs() >= seconds {
+            let n = delta / seconds;
+            let plural = if n.abs() == 1 { "" } else { "s" };
+            return if delta > 0 {
+                format!("in {} {}{}", n, unit, plural)
+            } else {
+                format!("{} {}{} ago", -n, unit, plural)
+            };
+        }
+    }
+
+    "now".to_string()
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -206,15 +603,62 @@ fn main() -> Result<(), Box<dyn Error>> {
         table.printstd();
     }
 
-    // Parse date
-    let datetime = args.get_naive_datetime()?;
-    let local = Local::from_offset(&FixedOffset::east_opt(0).unwrap());
-    let datetime = local.from_local_datetime(&datetime).unwrap();
-    println!("Formatting: {:?}", datetime);
+    let zone = args.zone()?;
+    let now = zone.now();
+
+    // Batch mode: one date/time string per line from a file or stdin
+    if let Some(path) = &args.file {
+        let reader: Box<dyn BufRead> = if path.as_os_str() == "-" {
+            Box::new(BufReader::new(io::stdin()))
+        } else {
+            Box::new(BufReader::new(File::open(path)?))
+        };
+
+        let mut results = Vec::new();
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match args
+                .get_naive_datetime(&line, now)
+                .and_then(|naive| zone.resolve(naive))
+            {
+                Ok(datetime) => {
+                    let unix = datetime.timestamp_millis() / 1000;
+                    let formatted = args.style.get_formatted(unix);
+                    // The preview goes to stderr so stdout stays one code per line for piping
+                    eprintln!("Preview: {}", args.style.preview(&datetime, args.clock));
+                    println!("{}", formatted);
+                    results.push(formatted);
+                }
+                Err(e) => eprintln!("line {}: {}", line_number + 1, e),
+            }
+        }
+
+        if args.copy_to_clipboard {
+            let mut ctx = ClipboardContext::new().unwrap();
+            ctx.set_contents(results.join("\n")).unwrap();
+            println!("{} results copied to clipboard!", results.len());
+        }
+
+        return Ok(());
+    }
+
+    // Resolve the instant to format, either from a parsed date/time string or a file's
+    // modification time
+    let datetime = match &args.reference {
+        Some(path) => zone.display(std::fs::metadata(path)?.modified()?.into()),
+        None => zone.resolve(args.get_naive_datetime(&args.input, now)?)?,
+    };
+    // Debug/preview info goes to stderr so stdout stays just the code, same as batch mode
+    eprintln!("Formatting: {:?}", datetime);
 
     // Get timestamp and formatted string
     let unix = datetime.timestamp_millis() / 1000;
     let formatted = args.style.get_formatted(unix);
+    eprintln!("Preview: {}", args.style.preview(&datetime, args.clock));
 
     // Output
     if args.copy_to_clipboard {